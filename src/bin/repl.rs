@@ -0,0 +1,31 @@
+use std::{env, fs, process};
+
+use lindenmayer::{builder::LSystem, parser::parse_lsystem, repl};
+
+fn main() {
+    let mut args = env::args().skip(1);
+    let path = match args.next() {
+        Some(path) => path,
+        None => {
+            eprintln!("usage: repl <lsystem-file> [depth]");
+            process::exit(1);
+        }
+    };
+    let depth: usize = args.next().and_then(|s| s.parse().ok()).unwrap_or(4);
+
+    let source = fs::read_to_string(&path).unwrap_or_else(|e| {
+        eprintln!("failed to read '{path}': {e}");
+        process::exit(1);
+    });
+
+    let mut rules = LSystem::new(String::new(), &[] as &[(char, &str)]);
+    let mut reader = parse_lsystem(&source, &mut rules, depth).unwrap_or_else(|e| {
+        eprintln!("failed to parse '{path}': {e}");
+        process::exit(1);
+    });
+
+    if let Err(e) = repl::run(&mut reader) {
+        eprintln!("repl error: {e}");
+        process::exit(1);
+    }
+}
@@ -1,4 +1,7 @@
-use std::{collections::HashMap, fmt::Display};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+};
 
 use crate::rng::SystemRng;
 use rand::{seq::SliceRandom, SeedableRng};
@@ -284,6 +287,151 @@ impl<'a> Iterator for LSystemBuilderStochastic<'_> {
     }
 }
 
+/// A single context-sensitive production: `left < predecessor > right -> successor`. Either side
+/// may be absent, in which case it matches any (or no) neighbor on that side.
+#[derive(Debug, Clone)]
+struct ContextRule {
+    left: Option<char>,
+    right: Option<char>,
+    successor: String,
+}
+
+/// A context-sensitive L-System (1L/2L): productions may additionally require a left and/or
+/// right neighbor symbol to match before firing. Finding the left neighbor skips over whole
+/// bracketed `[...]` branches; finding the right neighbor descends into the first branch it
+/// meets. Both skip any symbol in `ignore` (typically turtle commands like `+`/`-` that carry
+/// no botanical meaning). Because finding a neighbor requires looking at the whole bracketed
+/// string, rewriting is only available as [`LSystemContextSensitive::string`]; there is no
+/// streaming builder as there is for the context-free [`LSystem`].
+/// ```
+/// # use lindenmayer::builder::LSystemContextSensitive;
+/// let mut system = LSystemContextSensitive::new(String::from("baaaaaaa"), &[]);
+/// system.add_rule('a', Some('b'), None, "b");
+/// system.add_rule('a', Some('a'), None, "a");
+///
+/// assert_eq!("bbaaaaaa", system.string(1));
+/// ```
+#[derive(Debug, Clone)]
+pub struct LSystemContextSensitive {
+    pub axiom: String,
+    rules: HashMap<char, Vec<ContextRule>>,
+    ignore: HashSet<char>,
+}
+
+impl LSystemContextSensitive {
+    pub fn new(axiom: String, ignore: &[char]) -> Self {
+        LSystemContextSensitive {
+            axiom,
+            rules: HashMap::new(),
+            ignore: ignore.iter().copied().collect(),
+        }
+    }
+
+    /// Add a production `left < predecessor > right -> successor`. Pass `None` for a side to
+    /// match regardless of (or in the absence of) a neighbor there.
+    pub fn add_rule(
+        &mut self,
+        predecessor: char,
+        left: Option<char>,
+        right: Option<char>,
+        successor: &str,
+    ) {
+        self.rules.entry(predecessor).or_default().push(ContextRule {
+            left,
+            right,
+            successor: successor.to_string(),
+        });
+    }
+
+    /// Scan left from position `i`, skipping ignored symbols and whole balanced `[...]`
+    /// branches, to find the effective left neighbor.
+    fn left_context(chars: &[char], i: usize, ignore: &HashSet<char>) -> Option<char> {
+        let mut depth = 0usize;
+        let mut j = i;
+        while j > 0 {
+            j -= 1;
+            match chars[j] {
+                ']' => depth += 1,
+                '[' => {
+                    if depth > 0 {
+                        depth -= 1;
+                    }
+                    // The branch marker itself never counts as a neighbor; keep scanning
+                    // for whatever precedes the branch.
+                }
+                c if depth > 0 => {
+                    // Inside a branch that has already been skipped over.
+                    let _ = c;
+                }
+                c if ignore.contains(&c) => continue,
+                c => return Some(c),
+            }
+        }
+        None
+    }
+
+    /// Scan right from position `i`, skipping ignored symbols and descending into the first
+    /// optional `[...]` branch encountered, to find the effective right neighbor.
+    fn right_context(chars: &[char], i: usize, ignore: &HashSet<char>) -> Option<char> {
+        let mut depth = 0usize;
+        let mut j = i + 1;
+        while j < chars.len() {
+            match chars[j] {
+                '[' => depth += 1,
+                ']' => {
+                    if depth == 0 {
+                        return None;
+                    }
+                    depth -= 1;
+                }
+                c if ignore.contains(&c) => (),
+                c => return Some(c),
+            }
+            j += 1;
+        }
+        None
+    }
+
+    /// Among the candidate productions for a symbol, pick the most specific one that matches:
+    /// both contexts matching beats one context matching beats context-free.
+    fn best_match(
+        candidates: &[ContextRule],
+        left: Option<char>,
+        right: Option<char>,
+    ) -> Option<&ContextRule> {
+        candidates
+            .iter()
+            .filter(|r| {
+                r.left.is_none_or(|l| Some(l) == left) && r.right.is_none_or(|ri| Some(ri) == right)
+            })
+            .max_by_key(|r| r.left.is_some() as u8 + r.right.is_some() as u8)
+    }
+
+    /// Write the L-System, at the given depth, to a String.
+    pub fn string(&self, depth: usize) -> String {
+        let mut expression = self.axiom.clone();
+        for _ in 0..depth {
+            let chars: Vec<char> = expression.chars().collect();
+            let mut new = String::new();
+            for (i, c) in chars.iter().enumerate() {
+                match self.rules.get(c) {
+                    Some(candidates) => {
+                        let left = Self::left_context(&chars, i, &self.ignore);
+                        let right = Self::right_context(&chars, i, &self.ignore);
+                        match Self::best_match(candidates, left, right) {
+                            Some(rule) => new.push_str(&rule.successor),
+                            None => new.push(*c),
+                        }
+                    }
+                    None => new.push(*c),
+                }
+            }
+            expression = new;
+        }
+        expression
+    }
+}
+
 #[test]
 fn display_test() {
     use crate::builder::LSystem;
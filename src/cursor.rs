@@ -1,7 +1,8 @@
-use glam::Vec2;
+use glam::{Vec2, Vec3};
 
 /// A simple cursor with a position and direction in 2D space.
 #[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Cursor {
     position: Vec2,
     angle: Vec2,
@@ -63,3 +64,114 @@ impl Cursor {
         self.position += self.angle * distance
     }
 }
+
+/// Rotate `v` about the (assumed unit-length) `axis` by `radians` using Rodrigues' rotation
+/// formula.
+fn rotate_about_axis(v: Vec3, axis: Vec3, radians: f32) -> Vec3 {
+    let cos = radians.cos();
+    let sin = radians.sin();
+    v * cos + axis.cross(v) * sin + axis * axis.dot(v) * (1.0 - cos)
+}
+
+/// A cursor with a position and full orientation (heading/left/up frame) in 3D space, used to
+/// interpret classic bracketed L-systems as 3D turtle graphics.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Cursor3D {
+    position: Vec3,
+    heading: Vec3,
+    left: Vec3,
+    up: Vec3,
+}
+
+impl Cursor3D {
+    const DEG_TO_RAD: f32 = std::f32::consts::PI / 180.0;
+
+    /// Construct a Cursor3D from a position, a heading, and an approximate up vector. The frame
+    /// is orthonormalized from these two directions: `left` is derived as `up x heading` and
+    /// `up` is then recomputed as `heading x left` so the three axes are mutually perpendicular.
+    pub fn new(position: impl Into<Vec3>, heading: impl Into<Vec3>, up: impl Into<Vec3>) -> Self {
+        let heading = Into::into(heading)
+            .try_normalize()
+            .expect("unable to normalize heading during Cursor3D creation");
+        let left = Into::into(up)
+            .cross(heading)
+            .try_normalize()
+            .expect("unable to derive left axis during Cursor3D creation");
+        let up = heading.cross(left);
+        Cursor3D {
+            position: Into::into(position),
+            heading,
+            left,
+            up,
+        }
+    }
+
+    pub fn get_position(&self) -> Vec3 {
+        self.position
+    }
+
+    pub fn get_heading(&self) -> Vec3 {
+        self.heading
+    }
+
+    pub fn get_left(&self) -> Vec3 {
+        self.left
+    }
+
+    pub fn get_up(&self) -> Vec3 {
+        self.up
+    }
+
+    pub fn set_position(&mut self, position: Vec3) {
+        self.position = position
+    }
+
+    /// Rotate the heading and up axes about the left axis. A positive angle tips the heading
+    /// downward (toward the negative up axis); a negative angle tips it upward.
+    pub fn pitch(&mut self, radians: f32) {
+        self.heading = rotate_about_axis(self.heading, self.left, radians)
+            .try_normalize()
+            .expect("unable to normalize heading during pitch");
+        self.up = rotate_about_axis(self.up, self.left, radians)
+            .try_normalize()
+            .expect("unable to normalize up during pitch");
+    }
+
+    /// Rotate the heading about the up axis, turning the cursor left or right.
+    pub fn yaw(&mut self, radians: f32) {
+        self.heading = rotate_about_axis(self.heading, self.up, radians)
+            .try_normalize()
+            .expect("unable to normalize heading during yaw");
+        self.left = rotate_about_axis(self.left, self.up, radians)
+            .try_normalize()
+            .expect("unable to normalize left during yaw");
+    }
+
+    /// Rotate the left and up axes about the heading, rolling the cursor around its own axis.
+    pub fn roll(&mut self, radians: f32) {
+        self.left = rotate_about_axis(self.left, self.heading, radians)
+            .try_normalize()
+            .expect("unable to normalize left during roll");
+        self.up = rotate_about_axis(self.up, self.heading, radians)
+            .try_normalize()
+            .expect("unable to normalize up during roll");
+    }
+
+    pub fn pitch_degrees(&mut self, degrees: f32) {
+        self.pitch(degrees * Self::DEG_TO_RAD)
+    }
+
+    pub fn yaw_degrees(&mut self, degrees: f32) {
+        self.yaw(degrees * Self::DEG_TO_RAD)
+    }
+
+    pub fn roll_degrees(&mut self, degrees: f32) {
+        self.roll(degrees * Self::DEG_TO_RAD)
+    }
+
+    /// Move the Cursor3D forward the specified distance along its heading.
+    pub fn forward(&mut self, distance: f32) {
+        self.position += self.heading * distance
+    }
+}
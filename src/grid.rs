@@ -0,0 +1,237 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+};
+
+/// A square block of characters, used both as a rewrite pattern (left-hand side) and as a
+/// replacement (right-hand side) in [`GridRules`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Block {
+    size: usize,
+    cells: Vec<char>,
+}
+
+impl Block {
+    fn from_rows(rows: &[&str]) -> Self {
+        let size = rows.len();
+        let mut cells = Vec::with_capacity(size * size);
+        for row in rows {
+            assert_eq!(row.chars().count(), size, "block rows must form a square");
+            cells.extend(row.chars());
+        }
+        Block { size, cells }
+    }
+
+    fn get(&self, x: usize, y: usize) -> char {
+        self.cells[y * self.size + x]
+    }
+
+    /// Rotate the block 90 degrees clockwise.
+    fn rotate90(&self) -> Block {
+        let n = self.size;
+        let mut cells = vec!['.'; n * n];
+        for y in 0..n {
+            for x in 0..n {
+                cells[y * n + x] = self.get(y, n - 1 - x);
+            }
+        }
+        Block { size: n, cells }
+    }
+
+    /// Mirror the block horizontally.
+    fn flip_h(&self) -> Block {
+        let n = self.size;
+        let mut cells = vec!['.'; n * n];
+        for y in 0..n {
+            for x in 0..n {
+                cells[y * n + x] = self.get(n - 1 - x, y);
+            }
+        }
+        Block { size: n, cells }
+    }
+}
+
+/// All 8 symmetries of `block` (4 rotations, each with and without a horizontal flip), with
+/// duplicates (from blocks with their own internal symmetry) removed.
+fn symmetries(block: &Block) -> Vec<Block> {
+    let mut seen = HashSet::new();
+    let mut out = Vec::new();
+    let mut rotated = block.clone();
+    for _ in 0..4 {
+        for variant in [rotated.clone(), rotated.flip_h()] {
+            if seen.insert(variant.cells.clone()) {
+                out.push(variant);
+            }
+        }
+        rotated = rotated.rotate90();
+    }
+    out
+}
+
+/// A pattern's cells mapped to its replacement's size and cells, for one block size.
+type RuleTable = HashMap<Vec<char>, (usize, Vec<char>)>;
+
+/// A ruleset mapping a fixed-size square block pattern to a (typically larger) replacement
+/// block, used for parallel 2D block rewriting. Authors register one canonical orientation of a
+/// pattern and automatically get all 8 symmetries (4 rotations x horizontal flip) of it.
+#[derive(Debug, Clone, Default)]
+pub struct GridRules {
+    rules: HashMap<usize, RuleTable>,
+}
+
+impl GridRules {
+    pub fn new() -> Self {
+        GridRules::default()
+    }
+
+    /// Register a rule: the square `pattern` (given as rows, e.g. `&["##", ".."]`) rewrites to
+    /// the square `replacement`. All 8 symmetries of `pattern` are registered, each mapping to
+    /// the same `replacement`.
+    pub fn add_rule(&mut self, pattern: &[&str], replacement: &[&str]) {
+        let pattern = Block::from_rows(pattern);
+        let replacement = Block::from_rows(replacement);
+        let table = self.rules.entry(pattern.size).or_default();
+        for variant in symmetries(&pattern) {
+            table
+                .entry(variant.cells)
+                .or_insert((replacement.size, replacement.cells.clone()));
+        }
+    }
+}
+
+/// A 2D grid of characters, stored row-major.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Grid {
+    pub width: usize,
+    pub height: usize,
+    pub cells: Vec<char>,
+}
+
+impl Grid {
+    pub fn from_rows(rows: &[&str]) -> Self {
+        let height = rows.len();
+        let width = rows.first().map_or(0, |r| r.chars().count());
+        let mut cells = Vec::with_capacity(width * height);
+        for row in rows {
+            assert_eq!(row.chars().count(), width, "grid rows must have equal length");
+            cells.extend(row.chars());
+        }
+        Grid { width, height, cells }
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> char {
+        self.cells[y * self.width + x]
+    }
+
+    /// Iterate over the grid one row at a time.
+    pub fn rows(&self) -> impl Iterator<Item = &[char]> {
+        self.cells.chunks(self.width)
+    }
+}
+
+impl Display for Grid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            writeln!(f, "{}", row.iter().collect::<String>())?;
+        }
+        Ok(())
+    }
+}
+
+/// A parallel block-rewriting system over 2D grids: every generation the grid is tiled into
+/// `block_size` x `block_size` blocks (2 when the grid dimension is even, otherwise 3) and each
+/// block is independently replaced according to [`GridRules`], assembling a larger output grid.
+/// ```
+/// # use lindenmayer::grid::{Grid, GridLSystem, GridRules};
+/// let mut rules = GridRules::new();
+/// rules.add_rule(&["##", ".."], &["###", "...", "..."]);
+///
+/// let axiom = Grid::from_rows(&["##", ".."]);
+/// let system = GridLSystem::new(axiom, rules);
+///
+/// assert_eq!("###\n...\n...\n", system.grid(1).to_string());
+/// ```
+#[derive(Debug, Clone)]
+pub struct GridLSystem {
+    pub axiom: Grid,
+    rules: GridRules,
+}
+
+impl GridLSystem {
+    pub fn new(axiom: Grid, rules: GridRules) -> Self {
+        GridLSystem { axiom, rules }
+    }
+
+    fn block_size(dim: usize) -> usize {
+        if dim.is_multiple_of(2) {
+            2
+        } else if dim.is_multiple_of(3) {
+            3
+        } else {
+            panic!("grid dimension {dim} is not evenly divisible into 2x2 or 3x3 blocks")
+        }
+    }
+
+    fn rewrite_once(&self, grid: &Grid) -> Grid {
+        let n = Self::block_size(grid.width);
+        assert_eq!(
+            n,
+            Self::block_size(grid.height),
+            "grid width and height must decompose into the same block size"
+        );
+        let table = self
+            .rules
+            .rules
+            .get(&n)
+            .unwrap_or_else(|| panic!("no rules registered for {n}x{n} blocks"));
+        let m = table.values().next().expect("rule table for this block size is empty").0;
+
+        let blocks_x = grid.width / n;
+        let blocks_y = grid.height / n;
+        let out_width = blocks_x * m;
+        let mut cells = vec!['.'; out_width * blocks_y * m];
+
+        for by in 0..blocks_y {
+            for bx in 0..blocks_x {
+                let mut pattern = Vec::with_capacity(n * n);
+                for y in 0..n {
+                    for x in 0..n {
+                        pattern.push(grid.get(bx * n + x, by * n + y));
+                    }
+                }
+                let (out_size, replacement) = table
+                    .get(&pattern)
+                    .unwrap_or_else(|| panic!("no rule matches the block at ({bx}, {by})"));
+                assert_eq!(*out_size, m, "all rules for a block size must share one output size");
+                for y in 0..m {
+                    for x in 0..m {
+                        cells[(by * m + y) * out_width + (bx * m + x)] = replacement[y * m + x];
+                    }
+                }
+            }
+        }
+
+        Grid {
+            width: out_width,
+            height: blocks_y * m,
+            cells,
+        }
+    }
+
+    /// Apply the block rewrite the given number of times and return the resulting grid. This is
+    /// memory-light only in the sense that it never keeps more than one generation around;
+    /// unlike the 1D string builder, a block rewrite always needs the whole prior grid in hand
+    /// before it can tile the next one.
+    pub fn grid(&self, depth: usize) -> Grid {
+        let mut grid = self.axiom.clone();
+        for _ in 0..depth {
+            grid = self.rewrite_once(&grid);
+        }
+        grid
+    }
+
+    /// The rows of the grid at the given depth.
+    pub fn rows(&self, depth: usize) -> Vec<Vec<char>> {
+        self.grid(depth).rows().map(|row| row.to_vec()).collect()
+    }
+}
@@ -1,10 +1,21 @@
 pub mod builder;
 pub mod cursor;
+pub mod grid;
+pub mod parametric;
+pub mod parser;
 pub mod reader;
+pub mod repl;
 pub mod rng;
 pub mod segment;
+pub mod svg;
+pub mod writer;
 
 pub use builder::{LSystem, LSystemBuilder, LSystemBuilderStochastic, LSystemStochastic};
-pub use cursor::Cursor;
-pub use reader::{Action, SymbolReader};
-pub use segment::Segment;
+pub use cursor::{Cursor, Cursor3D};
+pub use grid::{Grid, GridLSystem, GridRules};
+pub use parametric::{Module, ParametricLSystem};
+pub use parser::{parse_lsystem, ParseError};
+pub use reader::{Action, LSystemReader};
+pub use segment::{Segment, Segment3};
+pub use svg::{to_svg, to_svg_3d, SvgOptions};
+pub use writer::{write_lsystem, write_lsystem_context, write_lsystem_sequence, write_lsystem_stochastic};
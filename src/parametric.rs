@@ -0,0 +1,397 @@
+use std::collections::HashMap;
+
+/// A symbol together with the numeric parameters bound to it, e.g. `F(2.3)` or `A(1.0, 2.0)`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Module {
+    pub symbol: char,
+    pub params: Vec<f32>,
+}
+
+impl Module {
+    pub fn new(symbol: char, params: Vec<f32>) -> Self {
+        Module { symbol, params }
+    }
+
+    /// The number of parameters bound to this module, used to key productions.
+    pub fn arity(&self) -> usize {
+        self.params.len()
+    }
+}
+
+impl std::fmt::Display for Module {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.params.is_empty() {
+            write!(f, "{}", self.symbol)
+        } else {
+            let args: Vec<String> = self.params.iter().map(|p| p.to_string()).collect();
+            write!(f, "{}({})", self.symbol, args.join(","))
+        }
+    }
+}
+
+/// A token of a shunting-yard parsed arithmetic expression, kept in postfix order.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Token {
+    Num(f32),
+    Var(char),
+    Op(char),
+}
+
+/// An arithmetic expression over `+ - * /`, parameter references, and f32 literals, stored in
+/// postfix (reverse Polish) order so it can be evaluated with a simple stack machine.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expr(Vec<Token>);
+
+impl Expr {
+    /// Parse an infix expression such as `s/2`, `(x+1)*2`, or `-x+1` with the shunting-yard
+    /// algorithm. A `-` (or `+`) is treated as unary negation (a no-op for `+`) whenever it
+    /// appears where an operand is expected: at the start of the expression, after `(`, or after
+    /// another operator.
+    pub fn parse(s: &str) -> Expr {
+        let mut output = Vec::new();
+        let mut ops: Vec<char> = Vec::new();
+        let mut chars = s.chars().peekable();
+        let mut expect_operand = true;
+
+        fn precedence(op: char) -> u8 {
+            match op {
+                '+' | '-' => 1,
+                '*' | '/' => 2,
+                '~' => 3,
+                _ => 0,
+            }
+        }
+
+        while let Some(&c) = chars.peek() {
+            if c.is_whitespace() {
+                chars.next();
+            } else if c.is_ascii_digit() || c == '.' {
+                let mut num = String::new();
+                while let Some(&d) = chars.peek() {
+                    if d.is_ascii_digit() || d == '.' {
+                        num.push(d);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                output.push(Token::Num(num.parse().expect("invalid numeric literal in expression")));
+                expect_operand = false;
+            } else if c.is_alphabetic() {
+                output.push(Token::Var(c));
+                chars.next();
+                expect_operand = false;
+            } else if c == '(' {
+                ops.push(c);
+                chars.next();
+                expect_operand = true;
+            } else if c == ')' {
+                while let Some(&top) = ops.last() {
+                    if top == '(' {
+                        break;
+                    }
+                    output.push(Token::Op(ops.pop().unwrap()));
+                }
+                ops.pop();
+                chars.next();
+                expect_operand = false;
+            } else if expect_operand && (c == '-' || c == '+') {
+                // Unary sign: `-` negates, `+` is a no-op, so only `-` needs an op token.
+                if c == '-' {
+                    ops.push('~');
+                }
+                chars.next();
+            } else {
+                while let Some(&top) = ops.last() {
+                    if top != '(' && precedence(top) >= precedence(c) {
+                        output.push(Token::Op(ops.pop().unwrap()));
+                    } else {
+                        break;
+                    }
+                }
+                ops.push(c);
+                chars.next();
+                expect_operand = true;
+            }
+        }
+        while let Some(op) = ops.pop() {
+            output.push(Token::Op(op));
+        }
+        Expr(output)
+    }
+
+    /// Evaluate the expression against an environment binding parameter names to values.
+    pub fn eval(&self, env: &HashMap<char, f32>) -> f32 {
+        let mut stack: Vec<f32> = Vec::new();
+        for token in &self.0 {
+            match token {
+                Token::Num(n) => stack.push(*n),
+                Token::Var(v) => stack.push(*env.get(v).unwrap_or_else(|| {
+                    panic!("unbound parameter '{v}' while evaluating expression")
+                })),
+                Token::Op('~') => {
+                    let operand = stack.pop().expect("malformed expression: missing operand");
+                    stack.push(-operand)
+                }
+                Token::Op(op) => {
+                    let rhs = stack.pop().expect("malformed expression: missing operand");
+                    let lhs = stack.pop().expect("malformed expression: missing operand");
+                    stack.push(match op {
+                        '+' => lhs + rhs,
+                        '-' => lhs - rhs,
+                        '*' => lhs * rhs,
+                        '/' => lhs / rhs,
+                        _ => panic!("unsupported operator '{op}' in expression"),
+                    })
+                }
+            }
+        }
+        stack.pop().expect("malformed expression: no result")
+    }
+}
+
+/// A comparison operator used by production guards.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CompareOp {
+    Lt,
+    Gt,
+    Le,
+    Ge,
+    Eq,
+    Ne,
+}
+
+/// A boolean guard of the form `lhs OP rhs`, evaluated over the formal parameters of a module.
+#[derive(Debug, Clone)]
+pub struct Guard {
+    lhs: Expr,
+    op: CompareOp,
+    rhs: Expr,
+}
+
+impl Guard {
+    /// Parse a guard such as `s>1` or `x <= y*2`.
+    pub fn parse(s: &str) -> Guard {
+        for (token, op) in [
+            (">=", CompareOp::Ge),
+            ("<=", CompareOp::Le),
+            ("==", CompareOp::Eq),
+            ("!=", CompareOp::Ne),
+            (">", CompareOp::Gt),
+            ("<", CompareOp::Lt),
+        ] {
+            if let Some(pos) = s.find(token) {
+                let lhs = Expr::parse(&s[..pos]);
+                let rhs = Expr::parse(&s[pos + token.len()..]);
+                return Guard { lhs, op, rhs };
+            }
+        }
+        panic!("guard '{s}' has no recognized comparison operator")
+    }
+
+    pub fn eval(&self, env: &HashMap<char, f32>) -> bool {
+        let lhs = self.lhs.eval(env);
+        let rhs = self.rhs.eval(env);
+        match self.op {
+            CompareOp::Lt => lhs < rhs,
+            CompareOp::Gt => lhs > rhs,
+            CompareOp::Le => lhs <= rhs,
+            CompareOp::Ge => lhs >= rhs,
+            CompareOp::Eq => lhs == rhs,
+            CompareOp::Ne => lhs != rhs,
+        }
+    }
+}
+
+/// A successor module whose parameters are expressions over the formal parameters of the
+/// production that produced it, rather than concrete values.
+#[derive(Debug, Clone)]
+pub struct ModuleTemplate {
+    pub symbol: char,
+    pub params: Vec<Expr>,
+}
+
+impl ModuleTemplate {
+    fn instantiate(&self, env: &HashMap<char, f32>) -> Module {
+        Module::new(self.symbol, self.params.iter().map(|e| e.eval(env)).collect())
+    }
+}
+
+/// A parametric production: `formals : guard -> successor`, keyed externally by symbol and arity.
+#[derive(Debug, Clone)]
+pub struct Production {
+    formals: Vec<char>,
+    guard: Option<Guard>,
+    successor: Vec<ModuleTemplate>,
+}
+
+impl Production {
+    /// Build a production from a formal parameter list, an optional guard expression, and a
+    /// successor string such as `F(s)A(s/2)`.
+    pub fn new(formals: &[char], guard: Option<&str>, successor: &str) -> Self {
+        Production {
+            formals: formals.to_vec(),
+            guard: guard.map(Guard::parse),
+            successor: parse_module_templates(successor),
+        }
+    }
+
+    fn bind(&self, params: &[f32]) -> HashMap<char, f32> {
+        self.formals.iter().copied().zip(params.iter().copied()).collect()
+    }
+
+    fn matches(&self, params: &[f32]) -> bool {
+        if self.formals.len() != params.len() {
+            return false;
+        }
+        match &self.guard {
+            Some(g) => g.eval(&self.bind(params)),
+            None => true,
+        }
+    }
+}
+
+/// Parse a sequence of modules with concrete numeric parameters, e.g. `A(1.0)F(2.3)[X(1.0)]`.
+pub fn parse_modules(s: &str) -> Vec<Module> {
+    let mut modules = Vec::new();
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        let mut params = Vec::new();
+        if chars.peek() == Some(&'(') {
+            chars.next();
+            let mut arg = String::new();
+            loop {
+                match chars.next() {
+                    Some(',') => {
+                        params.push(arg.trim().parse().expect("invalid parameter literal"));
+                        arg.clear();
+                    }
+                    Some(')') => {
+                        if !arg.trim().is_empty() {
+                            params.push(arg.trim().parse().expect("invalid parameter literal"));
+                        }
+                        break;
+                    }
+                    Some(other) => arg.push(other),
+                    None => panic!("unterminated parameter list for symbol '{c}'"),
+                }
+            }
+        }
+        modules.push(Module::new(c, params));
+    }
+    modules
+}
+
+fn parse_module_templates(s: &str) -> Vec<ModuleTemplate> {
+    let mut templates = Vec::new();
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        let mut params = Vec::new();
+        if chars.peek() == Some(&'(') {
+            chars.next();
+            let mut arg = String::new();
+            let mut depth = 0;
+            loop {
+                match chars.next() {
+                    Some('(') => {
+                        depth += 1;
+                        arg.push('(');
+                    }
+                    Some(')') if depth > 0 => {
+                        depth -= 1;
+                        arg.push(')');
+                    }
+                    Some(',') if depth == 0 => {
+                        params.push(Expr::parse(arg.trim()));
+                        arg.clear();
+                    }
+                    Some(')') => {
+                        if !arg.trim().is_empty() {
+                            params.push(Expr::parse(arg.trim()));
+                        }
+                        break;
+                    }
+                    Some(other) => arg.push(other),
+                    None => panic!("unterminated parameter list for symbol '{c}'"),
+                }
+            }
+        }
+        templates.push(ModuleTemplate { symbol: c, params });
+    }
+    templates
+}
+
+/// A parametric L-System: symbols carry `Vec<f32>` parameters, and productions may be guarded
+/// by a boolean expression over those parameters.
+/// ```
+/// # use lindenmayer::parametric::ParametricLSystem;
+/// let mut system = ParametricLSystem::new("A(1.0)");
+/// system.add_rule('A', &['s'], Some("s<10"), "F(s)A(s*2)");
+///
+/// let result = system.string(3);
+/// assert_eq!("F(1)F(2)F(4)A(8)", result.iter().map(|m| m.to_string()).collect::<Vec<_>>().join(""));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ParametricLSystem {
+    pub axiom: Vec<Module>,
+    rules: HashMap<(char, usize), Vec<Production>>,
+}
+
+impl ParametricLSystem {
+    pub fn new(axiom: &str) -> Self {
+        ParametricLSystem {
+            axiom: parse_modules(axiom),
+            rules: HashMap::new(),
+        }
+    }
+
+    /// Add a production for `symbol` with the given formal parameter names. The arity of the
+    /// production (the number of formals) determines which modules it can match.
+    pub fn add_rule(&mut self, symbol: char, formals: &[char], guard: Option<&str>, successor: &str) {
+        self.rules
+            .entry((symbol, formals.len()))
+            .or_default()
+            .push(Production::new(formals, guard, successor));
+    }
+
+    fn rewrite_once(&self, modules: &[Module]) -> Vec<Module> {
+        let mut next = Vec::with_capacity(modules.len());
+        for module in modules {
+            let key = (module.symbol, module.arity());
+            let production = self
+                .rules
+                .get(&key)
+                .and_then(|candidates| candidates.iter().find(|p| p.matches(&module.params)));
+            match production {
+                Some(p) => {
+                    let env = p.bind(&module.params);
+                    next.extend(p.successor.iter().map(|t| t.instantiate(&env)));
+                }
+                None => next.push(module.clone()),
+            }
+        }
+        next
+    }
+
+    /// Apply the productions the given number of times and return the resulting modules.
+    pub fn string(&self, depth: usize) -> Vec<Module> {
+        let mut modules = self.axiom.clone();
+        for _ in 0..depth {
+            modules = self.rewrite_once(&modules);
+        }
+        modules
+    }
+}
+
+#[test]
+fn unary_minus_in_successor_expression() {
+    let env = HashMap::from([('s', 5.0)]);
+    assert_eq!(-5.0, Expr::parse("-s").eval(&env));
+    assert_eq!(-4.0, Expr::parse("-s+1").eval(&env));
+    assert_eq!(-30.0, Expr::parse("-(s-1)*7.5").eval(&env));
+
+    let mut system = ParametricLSystem::new("A(1.0)");
+    system.add_rule('A', &['s'], None, "F(-s)");
+    let result = system.string(1);
+    assert_eq!("F(-1)", result.iter().map(|m| m.to_string()).collect::<Vec<_>>().join(""));
+}
@@ -0,0 +1,218 @@
+use std::{collections::HashMap, fmt::Display};
+
+use rand::{seq::SliceRandom, SeedableRng};
+
+use crate::{
+    builder::{LSystem, LSystemBuilder},
+    cursor::Cursor,
+    reader::{Action, LSystemReader},
+    rng::SystemRng,
+};
+
+/// An error produced while parsing a textual L-system definition, carrying the line and column
+/// at which the problem was found.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+fn error(line: usize, text: &str, needle: &str, message: impl Into<String>) -> ParseError {
+    let column = text.find(needle).map(|i| i + 1).unwrap_or(1);
+    ParseError {
+        line,
+        column,
+        message: message.into(),
+    }
+}
+
+fn parse_action(line: usize, text: &str, body: &str) -> Result<Action, ParseError> {
+    let mut parts = body.split_whitespace();
+    let command = parts
+        .next()
+        .ok_or_else(|| error(line, text, body, "expected an action after '->'"))?;
+    match command {
+        "draw" => {
+            let dist = parts
+                .next()
+                .ok_or_else(|| error(line, text, body, "'draw' expects a distance"))?;
+            let dist: f32 = dist
+                .parse()
+                .map_err(|_| error(line, text, dist, format!("'{dist}' is not a number")))?;
+            Ok(Action::DrawForward(dist))
+        }
+        "move" => {
+            let dist = parts
+                .next()
+                .ok_or_else(|| error(line, text, body, "'move' expects a distance"))?;
+            let dist: f32 = dist
+                .parse()
+                .map_err(|_| error(line, text, dist, format!("'{dist}' is not a number")))?;
+            Ok(Action::MoveForward(dist))
+        }
+        "rotate" => {
+            let degrees = parts
+                .next()
+                .ok_or_else(|| error(line, text, body, "'rotate' expects an angle in degrees"))?;
+            let degrees: f32 = degrees
+                .parse()
+                .map_err(|_| error(line, text, degrees, format!("'{degrees}' is not a number")))?;
+            Ok(Action::RotateDeg(degrees))
+        }
+        "push" => Ok(Action::PushCursor),
+        "pop" => Ok(Action::PopCursor),
+        "pushpos" => Ok(Action::PushPosition),
+        "poppos" => Ok(Action::PopPosition),
+        "pushangle" => Ok(Action::PushAngle),
+        "popangle" => Ok(Action::PopAngle),
+        "none" => Ok(Action::None),
+        other => Err(error(
+            line,
+            text,
+            other,
+            format!("unrecognized action '{other}'"),
+        )),
+    }
+}
+
+fn parse_point(line: usize, text: &str, s: &str) -> Result<(f32, f32), ParseError> {
+    let s = s.trim().trim_start_matches('(').trim_end_matches(')');
+    let (x, y) = s
+        .split_once(',')
+        .ok_or_else(|| error(line, text, s, format!("expected '(x,y)', found '{s}'")))?;
+    let x: f32 = x
+        .trim()
+        .parse()
+        .map_err(|_| error(line, text, x, format!("'{x}' is not a number")))?;
+    let y: f32 = y
+        .trim()
+        .parse()
+        .map_err(|_| error(line, text, y, format!("'{y}' is not a number")))?;
+    Ok((x, y))
+}
+
+/// Parse a single rule successor, resolving any stochastic alternatives (`0.7:A | 0.3:B`) by a
+/// one-time weighted random choice, since the resulting [`LSystemBuilder`] rewrites
+/// deterministically.
+fn parse_successor(
+    line: usize,
+    text: &str,
+    body: &str,
+    rng: &mut SystemRng,
+) -> Result<String, ParseError> {
+    if !body.contains(':') {
+        return Ok(body.trim().to_string());
+    }
+    let mut alternatives = Vec::new();
+    for alt in body.split('|') {
+        let (weight, successor) = alt
+            .split_once(':')
+            .ok_or_else(|| error(line, text, alt, format!("expected 'weight:successor' in '{alt}'")))?;
+        let weight: f32 = weight
+            .trim()
+            .parse()
+            .map_err(|_| error(line, text, weight, format!("'{weight}' is not a number")))?;
+        alternatives.push((successor.trim().to_string(), weight));
+    }
+    let chosen = alternatives
+        .choose_weighted(rng, |(_, w)| *w)
+        .map_err(|e| error(line, text, body, e.to_string()))?;
+    Ok(chosen.0.clone())
+}
+
+/// Parse a textual L-system definition into a ready-to-run [`LSystemReader`].
+///
+/// Supported lines are:
+/// - `axiom: X` the starting string
+/// - `rule: X = F[X][+DX]-DX` a context-free production
+/// - `rule: X = 0.7:A | 0.3:B` a stochastic production (one alternative is chosen per parse)
+/// - `F -> draw 40` bind a symbol to a turtle action (`draw`, `move`, `rotate`, `push`, `pop`,
+///   `pushpos`, `poppos`, `pushangle`, `popangle`, or `none`)
+/// - `start: (0,-200) heading (0,1)` the initial cursor position and heading
+///
+/// Blank lines and lines starting with `#` are ignored. The returned `LSystemReader` borrows
+/// from `rules`, so the caller provides the storage for it (mirroring [`LSystem::builder`]).
+/// ```
+/// # use lindenmayer::{builder::LSystem, parser::parse_lsystem};
+/// let source = "\
+/// axiom: X
+/// rule: X = F[X][+DX]-DX
+/// rule: D = F
+/// F -> draw 40
+/// + -> rotate -25
+/// - -> rotate 25
+/// [ -> push
+/// ] -> pop
+/// start: (0,-200) heading (0,1)
+/// ";
+/// let mut rules = LSystem::new(String::new(), &[] as &[(char, &str)]);
+/// let mut reader = parse_lsystem(source, &mut rules, 2).unwrap();
+/// while reader.step().is_some() {}
+/// assert!(!reader.segments.is_empty());
+/// ```
+pub fn parse_lsystem<'a>(
+    source: &str,
+    rules: &'a mut LSystem,
+    depth: usize,
+) -> Result<LSystemReader<'a>, ParseError> {
+    let mut axiom = String::new();
+    let mut rule_pairs: Vec<(char, String)> = Vec::new();
+    let mut actions = HashMap::new();
+    let mut start_position = (0.0_f32, 0.0_f32);
+    let mut start_heading = (0.0_f32, 1.0_f32);
+    let mut rng = SystemRng::from_entropy();
+
+    for (line_no, raw_line) in source.lines().enumerate() {
+        let line = line_no + 1;
+        let text = raw_line.trim();
+        if text.is_empty() || text.starts_with('#') {
+            continue;
+        }
+
+        if let Some(rest) = text.strip_prefix("axiom:") {
+            axiom = rest.trim().to_string();
+        } else if let Some(rest) = text.strip_prefix("rule:") {
+            let (symbol, successor) = rest
+                .split_once('=')
+                .ok_or_else(|| error(line, text, rest, "expected 'rule: X = successor'"))?;
+            let symbol = symbol.trim();
+            let mut chars = symbol.chars();
+            let symbol = chars
+                .next()
+                .filter(|_| chars.next().is_none())
+                .ok_or_else(|| error(line, text, symbol, format!("'{symbol}' is not a single symbol")))?;
+            let successor = parse_successor(line, text, successor.trim(), &mut rng)?;
+            rule_pairs.push((symbol, successor));
+        } else if let Some(rest) = text.strip_prefix("start:") {
+            let (position, heading) = rest
+                .split_once("heading")
+                .ok_or_else(|| error(line, text, rest, "expected 'start: (x,y) heading (x,y)'"))?;
+            start_position = parse_point(line, text, position)?;
+            start_heading = parse_point(line, text, heading)?;
+        } else if let Some((symbol, action)) = text.split_once("->") {
+            let symbol = symbol.trim();
+            let mut chars = symbol.chars();
+            let symbol = chars
+                .next()
+                .filter(|_| chars.next().is_none())
+                .ok_or_else(|| error(line, text, symbol, format!("'{symbol}' is not a single symbol")))?;
+            actions.insert(symbol, parse_action(line, text, action.trim())?);
+        } else {
+            return Err(error(line, text, text, format!("could not parse line '{text}'")));
+        }
+    }
+
+    *rules = LSystem::new(axiom, &rule_pairs);
+    let cursor = Cursor::new(start_position, start_heading);
+    let builder: LSystemBuilder<'a> = LSystemBuilder::new(rules, depth);
+    Ok(LSystemReader::new(builder, actions, cursor))
+}
@@ -2,16 +2,27 @@ use std::collections::HashMap;
 
 use glam::Vec2;
 
-use crate::{builder::LSystemBuilder, cursor::Cursor, segment::Segment};
+use crate::{
+    builder::LSystemBuilder,
+    cursor::{Cursor, Cursor3D},
+    parametric::Module,
+    segment::{Segment, Segment3},
+    svg::{self, SvgOptions},
+};
 
 /// Actions when reading the L-System
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Action {
     /// Do nothing
     None,
     /// Do nothing but report that symbol isn't recognized
     Unknown,
     /// Custom action
+    ///
+    /// Excluded from (de)serialization: `&'static str` has no `Deserialize` impl, and a
+    /// runtime-constructed value couldn't be `'static` anyway.
+    #[cfg_attr(feature = "serde", serde(skip))]
     Custom(&'static str),
     /// Move the Cursor forward the specified distance
     MoveForward(f32),
@@ -39,9 +50,91 @@ pub enum Action {
     PushAngle,
     /// Pop the top item of self.angles and replace the Cursor's angle with it
     PopAngle,
+    /// Move the 3D cursor forward and save a Segment3 between the positions to self.segments3d.
+    /// The standard turtle commands for this are `F` reused in a 3D grammar.
+    DrawForward3D(f32),
+    /// Pitch the 3D cursor (rotate heading/up about the left axis) by an angle in degrees.
+    /// The standard turtle commands for this are `&` (pitch down) and `^` (pitch up).
+    PitchDeg(f32),
+    /// Roll the 3D cursor (rotate left/up about the heading axis) by an angle in degrees.
+    /// The standard turtle commands for this are `\` (roll left) and `/` (roll right).
+    RollDeg(f32),
+    /// Yaw the 3D cursor (rotate heading/left about the up axis) by an angle in degrees.
+    /// The standard turtle commands for this are `+` (yaw left) and `-` (yaw right) in a 3D
+    /// grammar, or `|` to turn around 180 degrees.
+    YawDeg(f32),
+}
+
+impl Action {
+    /// Return a copy of this action with its distance/angle parameter replaced by `module`'s
+    /// first actual parameter, if it has one and this action carries such a parameter; otherwise
+    /// return the action unchanged. Used by [`LSystemReader::step_module`] so a parametric
+    /// module like `F(2.5)` can drive `DrawForward` by its bound value.
+    fn with_param(self, module: &Module) -> Action {
+        let Some(&p) = module.params.first() else {
+            return self;
+        };
+        match self {
+            Action::MoveForward(_) => Action::MoveForward(p),
+            Action::DrawForward(_) => Action::DrawForward(p),
+            Action::RotateRad(_) => Action::RotateRad(p),
+            Action::RotateDeg(_) => Action::RotateDeg(p),
+            Action::DrawForward3D(_) => Action::DrawForward3D(p),
+            Action::PitchDeg(_) => Action::PitchDeg(p),
+            Action::RollDeg(_) => Action::RollDeg(p),
+            Action::YawDeg(_) => Action::YawDeg(p),
+            other => other,
+        }
+    }
+}
+
+/// The inverse of a single `step()`, recorded onto [`LSystemReader`]'s history stack so that
+/// [`LSystemReader::step_back`] can restore interpreter state exactly as it was before the step.
+/// This only ever undoes cursor/stack/segment state; it does not rewind the expression itself,
+/// since [`LSystemBuilder`]'s layered iterator has no way to go backward.
+#[derive(Debug, Copy, Clone)]
+enum Undo {
+    /// The step changed no interpreter state.
+    Noop,
+    /// Restore the 2D cursor to this value.
+    Cursor(Cursor),
+    /// Restore the 2D cursor to this value and pop the segment the step pushed.
+    CursorAndSegment(Cursor),
+    /// The step pushed the cursor onto `cursors`; undo by popping it back off.
+    PopCursors,
+    /// The step popped `popped` off `cursors` into the cursor, replacing `prior`; undo by
+    /// restoring `prior` and pushing `popped` back onto the stack.
+    PushCursors { prior: Cursor, popped: Cursor },
+    /// The step pushed the cursor's position onto `positions`; undo by popping it back off.
+    PopPositions,
+    /// The step popped `popped` off `positions` into the cursor's position, replacing `prior`.
+    PushPositions { prior: Cursor, popped: Vec2 },
+    /// The step pushed the cursor's angle onto `angles`; undo by popping it back off.
+    PopAngles,
+    /// The step popped `popped` off `angles` into the cursor's angle, replacing `prior`.
+    PushAngles { prior: Cursor, popped: Vec2 },
+    /// Restore the 3D cursor to this value.
+    Cursor3D(Cursor3D),
+    /// Restore the 3D cursor to this value and pop the segment the step pushed.
+    Cursor3DAndSegment(Cursor3D),
+}
+
+/// A snapshot of the geometry and cursor state produced by reading an L-system, detached from
+/// the borrowed [`LSystemBuilder`] expression so it can be saved (e.g. as JSON, with the `serde`
+/// feature enabled) and reloaded without re-deriving the system that produced it.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReaderSnapshot {
+    pub segments: Vec<Segment>,
+    pub cursors: Vec<Cursor>,
+    pub positions: Vec<Vec2>,
+    pub angles: Vec<Vec2>,
+    pub cursor: Cursor,
+    pub segments3d: Vec<Segment3>,
+    pub cursor3d: Cursor3D,
 }
 
-/// Interpret a sequence of symbols as actions in 2D space.
+/// Interpret a sequence of symbols as actions in 2D (or, via the `*3D` actions, 3D) space.
 pub struct LSystemReader<'a> {
     expression: LSystemBuilder<'a>,
     actions: HashMap<char, Action>,
@@ -50,6 +143,16 @@ pub struct LSystemReader<'a> {
     pub positions: Vec<Vec2>,
     pub angles: Vec<Vec2>,
     pub cursor: Cursor,
+    /// Geometry produced by `DrawForward3D`, kept separate from the 2D `segments` buffer.
+    pub segments3d: Vec<Segment3>,
+    /// The 3D cursor driven by `DrawForward3D`/`PitchDeg`/`RollDeg`/`YawDeg`.
+    pub cursor3d: Cursor3D,
+    /// Inverse of each `step()` taken so far, most recent last; consumed by `step_back`.
+    history: Vec<(Action, Undo)>,
+    /// A clone of `expression` as it was at construction, used to rewind in `reset`.
+    initial_expression: LSystemBuilder<'a>,
+    /// The cursor as it was at construction, used to rewind in `reset`.
+    initial_cursor: Cursor,
 }
 
 impl<'a> LSystemReader<'a> {
@@ -59,6 +162,8 @@ impl<'a> LSystemReader<'a> {
         cursor: Cursor,
     ) -> Self {
         LSystemReader {
+            initial_expression: expression.clone(),
+            initial_cursor: cursor,
             expression,
             actions,
             segments: Vec::new(),
@@ -66,6 +171,9 @@ impl<'a> LSystemReader<'a> {
             positions: Vec::new(),
             angles: Vec::new(),
             cursor,
+            segments3d: Vec::new(),
+            cursor3d: Cursor3D::new((0.0, 0.0, 0.0), (0.0, 1.0, 0.0), (0.0, 0.0, 1.0)),
+            history: Vec::new(),
         }
     }
 
@@ -73,66 +181,243 @@ impl<'a> LSystemReader<'a> {
     /// Returns None if the expression has been read completely
     pub fn step(&mut self) -> Option<Action> {
         if let Some(c) = self.expression.next() {
-            if let Some(a) = self.actions.get(&c) {
-                match a {
-                    Action::DrawForward(dist) => {
-                        let old_pos = self.cursor.get_position();
-                        self.cursor.forward(*dist);
-                        self.segments
-                            .push(Segment::from((old_pos, self.cursor.get_position())));
-                    }
-                    Action::MoveForward(dist) => self.cursor.forward(*dist),
-                    Action::DrawTo(pos) => {
-                        let old_pos = self.cursor.get_position();
-                        self.cursor.set_position(*pos);
-                        self.segments.push(Segment::from((old_pos, *pos)));
-                    }
-                    Action::MoveTo(pos) => self.cursor.set_position(*pos),
-                    Action::RotateRad(radians) => self.cursor.rotate(*radians),
-                    Action::RotateDeg(degrees) => self.cursor.rotate_degrees(*degrees),
-                    Action::SetAngle(angle) => self.cursor.set_angle(*angle),
-                    Action::PushCursor => self.cursors.push(self.cursor),
-                    Action::PopCursor => {
-                        self.cursor = self
-                            .cursors
-                            .pop()
-                            .expect("tried to pop from self.cursors when it was empty")
-                    }
-                    Action::PushPosition => self.positions.push(self.cursor.get_position()),
-                    Action::PopPosition => self.cursor.set_position(
-                        self.positions
-                            .pop()
-                            .expect("tried to pop from self.positions when it was empty"),
-                    ),
-                    Action::PushAngle => self.angles.push(self.cursor.get_angle()),
-                    Action::PopAngle => self.cursor.set_angle(
-                        self.angles
-                            .pop()
-                            .expect("tried to pop from self.angles when it was empty"),
-                    ),
-                    Action::None | Action::Unknown | Action::Custom(_) => (),
-                }
-                Some(*a)
+            if let Some(a) = self.actions.get(&c).copied() {
+                let undo = self.apply(a);
+                self.history.push((a, undo));
+                Some(a)
             } else {
+                self.history.push((Action::Unknown, Undo::Noop));
                 Some(Action::Unknown)
             }
         } else {
             None
         }
     }
+
+    /// Perform the action bound to a parametric [`Module`], using the module's first actual
+    /// parameter (if any) in place of the constant baked into the action table, so e.g. `F(2.5)`
+    /// draws forward by `2.5` rather than whatever distance `actions` associates with `F`. This
+    /// is the entry point for interpreting the output of [`crate::parametric::ParametricLSystem`];
+    /// unlike `step`, it does not read from `self.expression`, since a parametric system produces
+    /// its own `Vec<Module>` rather than a stream of bare characters.
+    pub fn step_module(&mut self, module: &Module) -> Option<Action> {
+        if let Some(a) = self.actions.get(&module.symbol).copied() {
+            let a = a.with_param(module);
+            let undo = self.apply(a);
+            self.history.push((a, undo));
+            Some(a)
+        } else {
+            self.history.push((Action::Unknown, Undo::Noop));
+            Some(Action::Unknown)
+        }
+    }
+
+    /// Perform `a`, mutating cursor/stack/segment state, and return the inverse needed to undo it.
+    fn apply(&mut self, a: Action) -> Undo {
+        match a {
+            Action::DrawForward(dist) => {
+                let old_cursor = self.cursor;
+                self.cursor.forward(dist);
+                self.segments
+                    .push(Segment::from((old_cursor.get_position(), self.cursor.get_position())));
+                Undo::CursorAndSegment(old_cursor)
+            }
+            Action::MoveForward(dist) => {
+                let old_cursor = self.cursor;
+                self.cursor.forward(dist);
+                Undo::Cursor(old_cursor)
+            }
+            Action::DrawTo(pos) => {
+                let old_cursor = self.cursor;
+                self.cursor.set_position(pos);
+                self.segments
+                    .push(Segment::from((old_cursor.get_position(), pos)));
+                Undo::CursorAndSegment(old_cursor)
+            }
+            Action::MoveTo(pos) => {
+                let old_cursor = self.cursor;
+                self.cursor.set_position(pos);
+                Undo::Cursor(old_cursor)
+            }
+            Action::RotateRad(radians) => {
+                let old_cursor = self.cursor;
+                self.cursor.rotate(radians);
+                Undo::Cursor(old_cursor)
+            }
+            Action::RotateDeg(degrees) => {
+                let old_cursor = self.cursor;
+                self.cursor.rotate_degrees(degrees);
+                Undo::Cursor(old_cursor)
+            }
+            Action::SetAngle(angle) => {
+                let old_cursor = self.cursor;
+                self.cursor.set_angle(angle);
+                Undo::Cursor(old_cursor)
+            }
+            Action::PushCursor => {
+                self.cursors.push(self.cursor);
+                Undo::PopCursors
+            }
+            Action::PopCursor => {
+                let prior = self.cursor;
+                let popped = self
+                    .cursors
+                    .pop()
+                    .expect("tried to pop from self.cursors when it was empty");
+                self.cursor = popped;
+                Undo::PushCursors { prior, popped }
+            }
+            Action::PushPosition => {
+                self.positions.push(self.cursor.get_position());
+                Undo::PopPositions
+            }
+            Action::PopPosition => {
+                let prior = self.cursor;
+                let popped = self
+                    .positions
+                    .pop()
+                    .expect("tried to pop from self.positions when it was empty");
+                self.cursor.set_position(popped);
+                Undo::PushPositions { prior, popped }
+            }
+            Action::PushAngle => {
+                self.angles.push(self.cursor.get_angle());
+                Undo::PopAngles
+            }
+            Action::PopAngle => {
+                let prior = self.cursor;
+                let popped = self
+                    .angles
+                    .pop()
+                    .expect("tried to pop from self.angles when it was empty");
+                self.cursor.set_angle(popped);
+                Undo::PushAngles { prior, popped }
+            }
+            Action::DrawForward3D(dist) => {
+                let old_cursor3d = self.cursor3d;
+                self.cursor3d.forward(dist);
+                self.segments3d.push(Segment3::from((
+                    old_cursor3d.get_position(),
+                    self.cursor3d.get_position(),
+                )));
+                Undo::Cursor3DAndSegment(old_cursor3d)
+            }
+            Action::PitchDeg(degrees) => {
+                let old_cursor3d = self.cursor3d;
+                self.cursor3d.pitch_degrees(degrees);
+                Undo::Cursor3D(old_cursor3d)
+            }
+            Action::RollDeg(degrees) => {
+                let old_cursor3d = self.cursor3d;
+                self.cursor3d.roll_degrees(degrees);
+                Undo::Cursor3D(old_cursor3d)
+            }
+            Action::YawDeg(degrees) => {
+                let old_cursor3d = self.cursor3d;
+                self.cursor3d.yaw_degrees(degrees);
+                Undo::Cursor3D(old_cursor3d)
+            }
+            Action::None | Action::Unknown | Action::Custom(_) => Undo::Noop,
+        }
+    }
+
+    /// Undo the most recently taken `step()`, restoring the cursor(s), stacks, and segment
+    /// buffers to their state before that step, and returning the `Action` that was undone.
+    /// Returns `None` if there is no step left to undo. This does not rewind the expression
+    /// itself, so a subsequent `step()` continues reading new symbols rather than replaying
+    /// the undone one.
+    pub fn step_back(&mut self) -> Option<Action> {
+        let (action, undo) = self.history.pop()?;
+        match undo {
+            Undo::Noop => (),
+            Undo::Cursor(cursor) => self.cursor = cursor,
+            Undo::CursorAndSegment(cursor) => {
+                self.cursor = cursor;
+                self.segments.pop();
+            }
+            Undo::PopCursors => {
+                self.cursors.pop();
+            }
+            Undo::PushCursors { prior, popped } => {
+                self.cursor = prior;
+                self.cursors.push(popped);
+            }
+            Undo::PopPositions => {
+                self.positions.pop();
+            }
+            Undo::PushPositions { prior, popped } => {
+                self.cursor = prior;
+                self.positions.push(popped);
+            }
+            Undo::PopAngles => {
+                self.angles.pop();
+            }
+            Undo::PushAngles { prior, popped } => {
+                self.cursor = prior;
+                self.angles.push(popped);
+            }
+            Undo::Cursor3D(cursor3d) => self.cursor3d = cursor3d,
+            Undo::Cursor3DAndSegment(cursor3d) => {
+                self.cursor3d = cursor3d;
+                self.segments3d.pop();
+            }
+        }
+        Some(action)
+    }
+
+    /// Rewind the reader to its state immediately after construction: the expression restarts
+    /// from the axiom, the cursor(s) return to their initial transform, and all segments, stacks,
+    /// and history are cleared.
+    pub fn reset(&mut self) {
+        self.expression = self.initial_expression.clone();
+        self.cursor = self.initial_cursor;
+        self.cursor3d = Cursor3D::new((0.0, 0.0, 0.0), (0.0, 1.0, 0.0), (0.0, 0.0, 1.0));
+        self.segments.clear();
+        self.cursors.clear();
+        self.positions.clear();
+        self.angles.clear();
+        self.segments3d.clear();
+        self.history.clear();
+    }
+
+    /// Capture the current geometry and cursor state as a [`ReaderSnapshot`] that can be saved
+    /// (and, with the `serde` feature enabled, serialized) independently of the reader itself.
+    pub fn snapshot(&self) -> ReaderSnapshot {
+        ReaderSnapshot {
+            segments: self.segments.clone(),
+            cursors: self.cursors.clone(),
+            positions: self.positions.clone(),
+            angles: self.angles.clone(),
+            cursor: self.cursor,
+            segments3d: self.segments3d.clone(),
+            cursor3d: self.cursor3d,
+        }
+    }
+
+    /// Render the accumulated 2D geometry (`self.segments`) as a standalone SVG document. See
+    /// [`svg::to_svg`] for the underlying bounding box and polyline grouping.
+    pub fn to_svg(&self, options: SvgOptions) -> String {
+        svg::to_svg(&self.segments, &options, None)
+    }
+
+    /// Render the accumulated 3D geometry (`self.segments3d`) as a standalone SVG document by
+    /// orthographically projecting onto the XY plane. See [`svg::to_svg_3d`].
+    pub fn to_svg_3d(&self, options: SvgOptions) -> String {
+        svg::to_svg_3d(&self.segments3d, &options, None)
+    }
 }
 
 #[test]
 fn from_builder() {
     use std::collections::HashMap;
 
-    use crate::builder::LSystemBuilder;
+    use crate::builder::{LSystem, LSystemBuilder};
 
-    let axiom = "X";
-    let rules = HashMap::from([('X', "F[X][+DX]-DX"), ('D', "F")]);
+    let rules = [('X', "F[X][+DX]-DX"), ('D', "F")];
     let depth = 3;
 
-    let e = LSystemBuilder::new(axiom, rules, depth);
+    let sys = LSystem::new(String::from("X"), &rules);
+    let e = LSystemBuilder::new(&sys, depth);
 
     let actions = HashMap::from([
         ('X', Action::None),
@@ -147,3 +432,115 @@ fn from_builder() {
 
     let _ = LSystemReader::new(e, actions, cursor);
 }
+
+#[test]
+fn step_back_and_reset() {
+    use std::collections::HashMap;
+
+    use crate::builder::{LSystem, LSystemBuilder};
+
+    let rules = [('F', "F[+F]")];
+    let depth = 1;
+
+    let sys = LSystem::new(String::from("F"), &rules);
+    let e = LSystemBuilder::new(&sys, depth);
+
+    let actions = HashMap::from([
+        ('F', Action::DrawForward(10.0)),
+        ('+', Action::RotateDeg(90.0)),
+        ('[', Action::PushCursor),
+        (']', Action::PopCursor),
+    ]);
+    let cursor = Cursor::new((0.0, 0.0), (0.0, 1.0));
+
+    let mut reader = LSystemReader::new(e, actions, cursor);
+
+    while reader.step().is_some() {}
+    assert_eq!(reader.segments.len(), 2);
+    assert!(reader.cursors.is_empty());
+
+    // Execution order is `F [ + F ]`, so undo proceeds in reverse: PopCursor, the second F's
+    // draw, RotateDeg, PushCursor, then the first F's draw.
+    let undone = reader.step_back();
+    assert!(matches!(undone, Some(Action::PopCursor)));
+    assert_eq!(reader.cursors.len(), 1);
+
+    assert!(matches!(reader.step_back(), Some(Action::DrawForward(10.0))));
+    assert_eq!(reader.segments.len(), 1);
+
+    assert!(matches!(reader.step_back(), Some(Action::RotateDeg(90.0))));
+
+    assert!(matches!(reader.step_back(), Some(Action::PushCursor)));
+    assert!(reader.cursors.is_empty());
+
+    assert!(matches!(reader.step_back(), Some(Action::DrawForward(10.0))));
+    assert!(reader.segments.is_empty());
+    assert_eq!(reader.cursor.get_position(), Vec2::new(0.0, 0.0));
+
+    assert_eq!(reader.step_back(), None);
+
+    reader.reset();
+    assert!(reader.segments.is_empty());
+    assert!(reader.cursors.is_empty());
+    assert_eq!(reader.cursor.get_position(), Vec2::new(0.0, 0.0));
+    assert!(reader.step().is_some());
+}
+
+#[test]
+fn snapshot_and_to_svg() {
+    use std::collections::HashMap;
+
+    use crate::builder::{LSystem, LSystemBuilder};
+
+    let rules = [('F', "FF")];
+    let depth = 1;
+
+    let sys = LSystem::new(String::from("F"), &rules);
+    let e = LSystemBuilder::new(&sys, depth);
+
+    let actions = HashMap::from([('F', Action::DrawForward(10.0))]);
+    let cursor = Cursor::new((0.0, 0.0), (0.0, 1.0));
+
+    let mut reader = LSystemReader::new(e, actions, cursor);
+    while reader.step().is_some() {}
+
+    let snapshot = reader.snapshot();
+    assert_eq!(snapshot.segments.len(), reader.segments.len());
+    assert_eq!(snapshot.cursor, reader.cursor);
+
+    let svg = reader.to_svg(SvgOptions::default());
+    assert!(svg.starts_with("<svg"));
+    assert!(svg.contains("polyline"));
+}
+
+#[test]
+fn step_module_uses_bound_parameter() {
+    use std::collections::HashMap;
+
+    use crate::{
+        builder::{LSystem, LSystemBuilder},
+        parametric::ParametricLSystem,
+    };
+
+    let mut system = ParametricLSystem::new("A(20.0)");
+    system.add_rule('A', &['s'], Some("s>0"), "F(s)A(s-10)");
+
+    let modules = system.string(2);
+
+    // A placeholder expression; step_module reads modules directly and never touches it.
+    let sys = LSystem::new(String::new(), &[] as &[(char, &str)]);
+    let e = LSystemBuilder::new(&sys, 1);
+    let actions = HashMap::from([
+        ('F', Action::DrawForward(0.0)),
+        ('A', Action::None),
+    ]);
+    let cursor = Cursor::new((0.0, 0.0), (0.0, 1.0));
+    let mut reader = LSystemReader::new(e, actions, cursor);
+
+    for module in &modules {
+        reader.step_module(module);
+    }
+
+    assert_eq!(reader.segments.len(), 2);
+    assert_eq!(reader.cursor.get_position(), Vec2::new(0.0, 30.0));
+}
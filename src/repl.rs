@@ -0,0 +1,100 @@
+use rustyline::error::ReadlineError;
+use rustyline::DefaultEditor;
+
+use crate::{
+    reader::LSystemReader,
+    svg::SvgOptions,
+};
+
+/// Run an interactive shell over `reader`, printing a prompt and dispatching commands until the
+/// user quits or sends EOF (Ctrl-D/Ctrl-C). Used by the `repl` binary to explore how a grammar
+/// parsed with [`crate::parser::parse_lsystem`] unfolds symbol-by-symbol.
+///
+/// Supported commands:
+/// - `step` / `step N` — perform one (or N) steps, printing the `Action` returned by each
+/// - `run` — step until the expression is exhausted
+/// - `back` / `back N` — undo one (or N) steps via [`LSystemReader::step_back`]
+/// - `cursor` — print the current cursor's position and angle
+/// - `stack` — print the cursor/position/angle stacks
+/// - `dump svg <path>` — write the current geometry to `<path>` as SVG
+/// - `reset` — rewind the reader to its initial state
+/// - `quit` / `exit` — leave the shell
+pub fn run(reader: &mut LSystemReader) -> rustyline::Result<()> {
+    let mut editor = DefaultEditor::new()?;
+    loop {
+        let line = match editor.readline("lsystem> ") {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) | Err(ReadlineError::Eof) => break,
+            Err(e) => return Err(e),
+        };
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        editor.add_history_entry(line)?;
+
+        let mut parts = line.split_whitespace();
+        match parts.next().unwrap_or("") {
+            "step" => {
+                let n: usize = parts.next().and_then(|s| s.parse().ok()).unwrap_or(1);
+                for _ in 0..n {
+                    match reader.step() {
+                        Some(action) => println!("{action:?}"),
+                        None => {
+                            println!("(end of expression)");
+                            break;
+                        }
+                    }
+                }
+            }
+            "run" => {
+                while let Some(action) = reader.step() {
+                    println!("{action:?}");
+                }
+            }
+            "back" => {
+                let n: usize = parts.next().and_then(|s| s.parse().ok()).unwrap_or(1);
+                for _ in 0..n {
+                    match reader.step_back() {
+                        Some(action) => println!("undid {action:?}"),
+                        None => {
+                            println!("(nothing to undo)");
+                            break;
+                        }
+                    }
+                }
+            }
+            "cursor" => println!(
+                "position: {:?}, angle: {:?}",
+                reader.cursor.get_position(),
+                reader.cursor.get_angle()
+            ),
+            "stack" => {
+                println!("cursors ({}): {:?}", reader.cursors.len(), reader.cursors);
+                println!(
+                    "positions ({}): {:?}",
+                    reader.positions.len(),
+                    reader.positions
+                );
+                println!("angles ({}): {:?}", reader.angles.len(), reader.angles);
+            }
+            "dump" => match (parts.next(), parts.next()) {
+                (Some("svg"), Some(path)) => {
+                    let svg = reader.to_svg(SvgOptions::default());
+                    match std::fs::write(path, svg) {
+                        Ok(()) => println!("wrote {path}"),
+                        Err(e) => println!("failed to write '{path}': {e}"),
+                    }
+                }
+                _ => println!("usage: dump svg <path>"),
+            },
+            "reset" => {
+                reader.reset();
+                println!("reset");
+            }
+            "quit" | "exit" => break,
+            other => println!("unrecognized command '{other}'"),
+        }
+    }
+    Ok(())
+}
@@ -1,6 +1,7 @@
-use glam::Vec2;
+use glam::{Vec2, Vec3};
 
 #[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Segment {
     pub start: Vec2,
     pub end: Vec2,
@@ -23,3 +24,37 @@ impl From<(Vec2, Vec2)> for Segment {
         }
     }
 }
+
+/// A line segment between two points in 3D space, the 3D counterpart of [`Segment`].
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Segment3 {
+    pub start: Vec3,
+    pub end: Vec3,
+}
+
+impl Segment3 {
+    pub fn new(start: impl Into<Vec3>, end: impl Into<Vec3>) -> Self {
+        Segment3 {
+            start: Into::into(start),
+            end: Into::into(end),
+        }
+    }
+}
+
+impl From<(Vec3, Vec3)> for Segment3 {
+    fn from(value: (Vec3, Vec3)) -> Self {
+        Self {
+            start: value.0,
+            end: value.1,
+        }
+    }
+}
+
+impl Segment3 {
+    /// Orthographically project onto the XY plane (drop Z) to get a 2D [`Segment`], e.g. for
+    /// rendering 3D turtle output with [`crate::svg::to_svg`], which is inherently a 2D format.
+    pub fn project_xy(&self) -> Segment {
+        Segment::new(self.start.truncate(), self.end.truncate())
+    }
+}
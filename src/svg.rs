@@ -0,0 +1,133 @@
+use glam::Vec2;
+
+use crate::segment::{Segment, Segment3};
+
+/// Options controlling how a segment buffer is turned into an SVG document.
+#[derive(Debug, Clone)]
+pub struct SvgOptions {
+    /// Extra space added around the bounding box of the geometry, in the same units as the
+    /// segment coordinates.
+    pub padding: f32,
+    pub stroke_width: f32,
+    pub stroke: String,
+    /// Flip the Y axis, since segment coordinates grow upward but SVG coordinates grow downward.
+    pub flip_y: bool,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        SvgOptions {
+            padding: 10.0,
+            stroke_width: 1.0,
+            stroke: String::from("black"),
+            flip_y: true,
+        }
+    }
+}
+
+/// Group consecutive segments into polylines by matching a segment's `end` against the next
+/// segment's `start`; a break in that chain (a pen-up jump) starts a new polyline.
+pub fn polylines(segments: &[Segment]) -> Vec<Vec<Vec2>> {
+    let mut lines: Vec<Vec<Vec2>> = Vec::new();
+    for segment in segments {
+        match lines.last_mut() {
+            Some(line) if line.last() == Some(&segment.start) => line.push(segment.end),
+            _ => lines.push(vec![segment.start, segment.end]),
+        }
+    }
+    lines
+}
+
+fn bounding_box(points: &[Vec2]) -> (Vec2, Vec2) {
+    let mut min = Vec2::splat(f32::INFINITY);
+    let mut max = Vec2::splat(f32::NEG_INFINITY);
+    for &p in points {
+        min = min.min(p);
+        max = max.max(p);
+    }
+    (min, max)
+}
+
+/// Render a segment buffer as a standalone SVG document, one `<polyline>` per connected run of
+/// segments. `colors`, if provided, is cycled across polylines in the order they are drawn;
+/// otherwise every polyline uses `options.stroke`.
+pub fn to_svg(segments: &[Segment], options: &SvgOptions, colors: Option<&[&str]>) -> String {
+    let lines = polylines(segments);
+    if lines.is_empty() {
+        return String::from("<svg xmlns=\"http://www.w3.org/2000/svg\"></svg>");
+    }
+
+    let lines: Vec<Vec<Vec2>> = lines
+        .into_iter()
+        .map(|line| {
+            line.into_iter()
+                .map(|p| if options.flip_y { Vec2::new(p.x, -p.y) } else { p })
+                .collect()
+        })
+        .collect();
+
+    let all_points: Vec<Vec2> = lines.iter().flatten().copied().collect();
+    let (min, max) = bounding_box(&all_points);
+    let size = max - min + Vec2::splat(options.padding * 2.0);
+    let min = min - Vec2::splat(options.padding);
+
+    let mut body = String::new();
+    for (i, line) in lines.iter().enumerate() {
+        let stroke = colors
+            .and_then(|cs| cs.get(i % cs.len().max(1)))
+            .copied()
+            .unwrap_or(&options.stroke);
+        let points: Vec<String> = line.iter().map(|p| format!("{},{}", p.x, p.y)).collect();
+        body.push_str(&format!(
+            "<polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"{}\"/>\n",
+            points.join(" "),
+            stroke,
+            options.stroke_width
+        ));
+    }
+
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\">\n{}</svg>",
+        min.x, min.y, size.x, size.y, body
+    )
+}
+
+/// Render a 3D segment buffer as a standalone SVG document by orthographically projecting onto
+/// the XY plane (see [`Segment3::project_xy`]) and delegating to [`to_svg`]. SVG has no native 3D
+/// support, so this is necessarily a flattened view of the geometry, not a full 3D renderer.
+pub fn to_svg_3d(segments: &[Segment3], options: &SvgOptions, colors: Option<&[&str]>) -> String {
+    let projected: Vec<Segment> = segments.iter().map(Segment3::project_xy).collect();
+    to_svg(&projected, options, colors)
+}
+
+#[test]
+fn view_box_pads_exactly_once_per_side() {
+    let segments = [Segment::new((0.0, 0.0), (10.0, 0.0))];
+    let options = SvgOptions {
+        padding: 5.0,
+        flip_y: false,
+        ..SvgOptions::default()
+    };
+
+    let svg = to_svg(&segments, &options, None);
+    let view_box = svg
+        .split("viewBox=\"")
+        .nth(1)
+        .and_then(|s| s.split('"').next())
+        .unwrap();
+    let values: Vec<f32> = view_box.split(' ').map(|s| s.parse().unwrap()).collect();
+
+    assert_eq!(values, vec![-5.0, -5.0, 20.0, 10.0]);
+}
+
+#[test]
+fn to_svg_3d_projects_out_the_z_axis() {
+    let segments3d = [Segment3::new((0.0, 0.0, 7.0), (10.0, 0.0, -3.0))];
+    let segments = [Segment::new((0.0, 0.0), (10.0, 0.0))];
+    let options = SvgOptions {
+        flip_y: false,
+        ..SvgOptions::default()
+    };
+
+    assert_eq!(to_svg_3d(&segments3d, &options, None), to_svg(&segments, &options, None));
+}
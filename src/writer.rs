@@ -75,6 +75,113 @@ pub fn write_lsystem_stochastic(
     expression
 }
 
+/// Compute the significant left neighbor of position `i`: scan left, skipping whole bracketed
+/// `[...]` subtrees and any symbol in `ignore`, and return the first symbol found.
+fn left_context(chars: &[char], i: usize, ignore: &[char]) -> Option<char> {
+    let mut depth = 0usize;
+    let mut j = i;
+    while j > 0 {
+        j -= 1;
+        match chars[j] {
+            ']' => depth += 1,
+            '[' => {
+                if depth > 0 {
+                    depth -= 1;
+                }
+            }
+            c if depth > 0 => {
+                let _ = c;
+            }
+            c if ignore.contains(&c) => continue,
+            c => return Some(c),
+        }
+    }
+    None
+}
+
+/// Compute the significant right neighbor of position `i`: scan right, skipping whole bracketed
+/// `[...]` subtrees and any symbol in `ignore`, to find the true sibling/successor symbol. A `]`
+/// closing the branch containing `i` means there is no right neighbor.
+fn right_context(chars: &[char], i: usize, ignore: &[char]) -> Option<char> {
+    let mut depth = 0usize;
+    let mut j = i + 1;
+    while j < chars.len() {
+        match chars[j] {
+            '[' => depth += 1,
+            ']' => {
+                if depth == 0 {
+                    return None;
+                }
+                depth -= 1;
+            }
+            c if depth > 0 => {
+                let _ = c;
+            }
+            c if ignore.contains(&c) => (),
+            c => return Some(c),
+        }
+        j += 1;
+    }
+    None
+}
+
+/// Apply context-sensitive (1L/2L) rules simultaneously, the number of generations specified,
+/// and return the resulting String. Each rule is `(predecessor, left, right, successor)`, read
+/// as `left < predecessor > right -> successor`; `left`/`right` of `None` match regardless of
+/// (or in the absence of) a neighbor on that side. The "neighbor" of a symbol is computed over
+/// the bracketed tree structure, not the raw char stream: scanning in either direction skips
+/// whole `[...]` subtrees to find the true sibling symbol, and any symbol in `ignore` (typically
+/// turtle commands like `+`/`-` that carry no botanical meaning) is skipped on both sides. Where
+/// several rules for the same predecessor match, the one with more pinned contexts wins; where
+/// none match, the predecessor is copied unchanged.
+pub fn write_lsystem_context(
+    axiom: &str,
+    rules: &[(char, Option<char>, Option<char>, &str)],
+    ignore: &[char],
+    depth: usize,
+) -> String {
+    let mut expression = String::from(axiom);
+    for _ in 0..depth {
+        let chars: Vec<char> = expression.chars().collect();
+        let mut new = String::new();
+        for (i, &c) in chars.iter().enumerate() {
+            let left = left_context(&chars, i, ignore);
+            let right = right_context(&chars, i, ignore);
+            let best = rules
+                .iter()
+                .filter(|(pred, l, r, _)| {
+                    *pred == c
+                        && l.is_none_or(|l| Some(l) == left)
+                        && r.is_none_or(|r| Some(r) == right)
+                })
+                .max_by_key(|(_, l, r, _)| l.is_some() as u8 + r.is_some() as u8);
+            match best {
+                Some((_, _, _, successor)) => new.push_str(successor),
+                None => new.push(c),
+            }
+        }
+        expression = new;
+    }
+    expression
+}
+
+#[test]
+fn context_test() {
+    let axiom = "baaaaaaa";
+    let rules = [('a', Some('b'), None, "b"), ('a', Some('a'), None, "a")];
+
+    assert_eq!("bbaaaaaa", write_lsystem_context(axiom, &rules, &[], 1));
+}
+
+#[test]
+fn context_test_skips_bracketed_branch() {
+    // The right neighbor of 'A' is the sibling 'C' after the whole `[B]` branch, not 'B'.
+    let axiom = "A[B]C";
+    let rules = [('A', None, Some('C'), "X")];
+
+    assert_eq!("X[B]C", write_lsystem_context(axiom, &rules, &[], 1));
+}
+
 #[test]
 fn validation_test() {
     use std::collections::HashMap;